@@ -1,6 +1,9 @@
+use std::convert::TryFrom;
 use std::time;
 use humantime;
 use chrono;
+use chrono::{Datelike, TimeZone, Timelike};
+use chrono_tz;
 
 pub fn duration_to_humatime(dur: time::Duration) -> String {
     humantime::format_duration(dur).to_string().clone()
@@ -12,4 +15,232 @@ pub fn timestamp_to_iso(mut stamp: u64) -> String {
     }
     let new_time = time::UNIX_EPOCH + time::Duration::new(stamp, 0);
     humantime::format_rfc3339(new_time).to_string()
+}
+
+pub fn parse_duration(input: &str) -> Result<(u64, u32), humantime::DurationError> {
+    let dur = humantime::parse_duration(input)?;
+    Ok((dur.as_secs(), dur.subsec_nanos()))
+}
+
+pub fn timestamp_to_iso_tz(stamp: u64, tz: &str) -> Result<String, chrono_tz::ParseError> {
+    let zone: chrono_tz::Tz = tz.parse()?;
+    let utc = chrono::Utc.timestamp(stamp as i64, 0);
+    Ok(utc.with_timezone(&zone).to_rfc3339())
+}
+
+/// Error returned by `timestamp_from_iso`: either the input wasn't valid
+/// RFC3339, or it parsed fine but names an instant before the Unix epoch,
+/// which can't be represented as the `u64` seconds this crate uses.
+pub enum FromIsoError {
+    Parse(chrono::ParseError),
+    OutOfRange,
+}
+
+impl std::fmt::Display for FromIsoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromIsoError::Parse(e) => write!(f, "{}", e),
+            FromIsoError::OutOfRange => write!(f, "timestamp is before the Unix epoch"),
+        }
+    }
+}
+
+impl From<chrono::ParseError> for FromIsoError {
+    fn from(e: chrono::ParseError) -> Self {
+        FromIsoError::Parse(e)
+    }
+}
+
+pub fn timestamp_from_iso(input: &str) -> Result<u64, FromIsoError> {
+    let parsed = chrono::DateTime::parse_from_rfc3339(input)?;
+    let secs = parsed.with_timezone(&chrono::Utc).timestamp();
+    u64::try_from(secs).map_err(|_| FromIsoError::OutOfRange)
+}
+
+/// The components of a calendar-aware difference between two timestamps,
+/// as produced by `precise_diff`. Adding `years` and `months` onto the earlier
+/// of the two timestamps without end-of-month clamping (a day the target month
+/// lacks rolls over into the next month), then `days`, `hours`, `minutes` and
+/// `seconds`, reproduces the later one exactly. `add_calendar` clamps to the
+/// month end instead, so it reproduces the later timestamp only when the
+/// earlier one's day of month exists in the month that the shift reaches.
+pub struct PreciseDiff {
+    pub years: u32,
+    pub months: u32,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub negative: bool,
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    let first_of_this_month = chrono::NaiveDate::from_ymd(year, month, 1);
+    let first_of_next_month = chrono::NaiveDate::from_ymd(next_year, next_month, 1);
+    (first_of_next_month - first_of_this_month).num_days() as u32
+}
+
+pub fn precise_diff(start: u64, end: u64) -> PreciseDiff {
+    let negative = start > end;
+    let (lo, hi) = if negative { (end, start) } else { (start, end) };
+    let lo_dt = chrono::NaiveDateTime::from_timestamp(lo as i64, 0);
+    let hi_dt = chrono::NaiveDateTime::from_timestamp(hi as i64, 0);
+
+    let mut year = hi_dt.year() - lo_dt.year();
+    let mut month = hi_dt.month() as i32 - lo_dt.month() as i32;
+    let mut day = hi_dt.day() as i32 - lo_dt.day() as i32;
+    let mut hour = hi_dt.hour() as i32 - lo_dt.hour() as i32;
+    let mut minute = hi_dt.minute() as i32 - lo_dt.minute() as i32;
+    let mut second = hi_dt.second() as i32 - lo_dt.second() as i32;
+
+    if second < 0 {
+        second += 60;
+        minute -= 1;
+    }
+    if minute < 0 {
+        minute += 60;
+        hour -= 1;
+    }
+    if hour < 0 {
+        hour += 24;
+        day -= 1;
+    }
+    let mut cursor_year = hi_dt.year();
+    let mut cursor_month = hi_dt.month();
+    while day < 0 {
+        if cursor_month == 1 {
+            cursor_month = 12;
+            cursor_year -= 1;
+        } else {
+            cursor_month -= 1;
+        }
+        day += days_in_month(cursor_year, cursor_month) as i32;
+        month -= 1;
+    }
+    while month < 0 {
+        month += 12;
+        year -= 1;
+    }
+
+    PreciseDiff {
+        years: year as u32,
+        months: month as u32,
+        days: day as u32,
+        hours: hour as u32,
+        minutes: minute as u32,
+        seconds: second as u32,
+        negative,
+    }
+}
+
+pub fn add_duration(stamp: u64, secs: u64) -> u64 {
+    stamp + secs
+}
+
+pub fn add_calendar(stamp: u64, years: i32, months: i32, days: i32) -> u64 {
+    let dt = chrono::NaiveDateTime::from_timestamp(stamp as i64, 0);
+
+    let total_months = dt.month0() as i32 + months + years * 12;
+    let new_year = dt.year() + total_months.div_euclid(12);
+    let new_month = total_months.rem_euclid(12) as u32 + 1;
+    let new_day = dt.day().min(days_in_month(new_year, new_month));
+
+    let new_date = chrono::NaiveDate::from_ymd(new_year, new_month, new_day);
+    let new_dt = chrono::NaiveDateTime::new(new_date, dt.time()) + chrono::Duration::days(days as i64);
+    new_dt.timestamp() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(year: i32, month: u32, day: u32) -> u64 {
+        chrono::NaiveDate::from_ymd(year, month, day)
+            .and_hms(0, 0, 0)
+            .timestamp() as u64
+    }
+
+    #[test]
+    fn precise_diff_borrows_across_a_short_february() {
+        // Jan 31 -> Mar 1 needs to borrow more than Feb 2023's 28 days,
+        // which used to leave `day` negative and wrap to a huge u32.
+        let start = epoch(2023, 1, 31);
+        let end = epoch(2023, 3, 1);
+        let diff = precise_diff(start, end);
+
+        assert!(!diff.negative);
+        assert!(diff.days < 31);
+        assert_eq!(add_calendar(start, 0, diff.months as i32, diff.days as i32), end);
+    }
+
+    #[test]
+    fn precise_diff_respects_leap_february() {
+        let start = epoch(2024, 1, 31);
+        let end = epoch(2024, 3, 1);
+        let diff = precise_diff(start, end);
+
+        assert!(!diff.negative);
+        assert!(diff.days < 31);
+        assert_eq!(add_calendar(start, 0, diff.months as i32, diff.days as i32), end);
+    }
+
+    #[test]
+    fn add_calendar_clamps_to_month_end() {
+        assert_eq!(add_calendar(epoch(2023, 1, 31), 0, 1, 0), epoch(2023, 2, 28));
+        assert_eq!(add_calendar(epoch(2024, 1, 31), 0, 1, 0), epoch(2024, 2, 29));
+    }
+
+    #[test]
+    fn from_iso_round_trips_with_to_iso() {
+        let stamp = epoch(2024, 1, 2);
+        let iso = timestamp_to_iso(stamp);
+        assert_eq!(timestamp_from_iso(&iso).ok(), Some(stamp));
+    }
+
+    #[test]
+    fn from_iso_rejects_malformed_input() {
+        assert!(timestamp_from_iso("not a timestamp").is_err());
+    }
+
+    #[test]
+    fn from_iso_rejects_pre_epoch_timestamps() {
+        assert!(timestamp_from_iso("1969-12-31T23:59:59Z").is_err());
+    }
+
+    #[test]
+    fn parse_duration_reads_humantime_strings() {
+        assert_eq!(parse_duration("2h30m").unwrap(), (2 * 3600 + 30 * 60, 0));
+        assert_eq!(parse_duration("1day 6h").unwrap(), (30 * 3600, 0));
+        assert_eq!(parse_duration("500ms").unwrap(), (0, 500_000_000));
+    }
+
+    #[test]
+    fn parse_duration_rejects_malformed_input() {
+        assert!(parse_duration("not a duration").is_err());
+    }
+
+    #[test]
+    fn timestamp_to_iso_tz_converts_to_the_named_zone() {
+        let stamp = chrono::Utc.ymd(2024, 1, 2).and_hms(15, 0, 0).timestamp() as u64;
+        assert_eq!(
+            timestamp_to_iso_tz(stamp, "America/New_York").unwrap(),
+            "2024-01-02T10:00:00-05:00"
+        );
+    }
+
+    #[test]
+    fn timestamp_to_iso_tz_rejects_unknown_zones() {
+        assert!(timestamp_to_iso_tz(0, "Not/AZone").is_err());
+    }
+
+    #[test]
+    fn add_duration_offsets_by_plain_seconds() {
+        assert_eq!(add_duration(epoch(2024, 1, 1), 3600), epoch(2024, 1, 1) + 3600);
+    }
+
+    #[test]
+    fn add_calendar_applies_a_plain_day_offset() {
+        assert_eq!(add_calendar(epoch(2024, 1, 1), 0, 0, 10), epoch(2024, 1, 11));
+    }
 }
\ No newline at end of file
@@ -20,6 +20,71 @@ fn to_iso(stamp: u64) -> PyResult<String> {
     Ok(time::timestamp_to_iso(stamp))
 }
 
+#[pyfunction]
+fn from_humantime(input: &str) -> PyResult<(u64, u32)> {
+    time::parse_duration(input)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+fn to_iso_tz(stamp: u64, tz: &str) -> PyResult<String> {
+    time::timestamp_to_iso_tz(stamp, tz)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+fn from_iso(s: &str) -> PyResult<u64> {
+    time::timestamp_from_iso(s)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
+
+#[pyclass]
+struct PreciseDiff {
+    #[pyo3(get)]
+    years: u32,
+    #[pyo3(get)]
+    months: u32,
+    #[pyo3(get)]
+    days: u32,
+    #[pyo3(get)]
+    hours: u32,
+    #[pyo3(get)]
+    minutes: u32,
+    #[pyo3(get)]
+    seconds: u32,
+    #[pyo3(get)]
+    negative: bool,
+}
+
+impl From<time::PreciseDiff> for PreciseDiff {
+    fn from(diff: time::PreciseDiff) -> Self {
+        PreciseDiff {
+            years: diff.years,
+            months: diff.months,
+            days: diff.days,
+            hours: diff.hours,
+            minutes: diff.minutes,
+            seconds: diff.seconds,
+            negative: diff.negative,
+        }
+    }
+}
+
+#[pyfunction]
+fn precise_diff(start: u64, end: u64) -> PyResult<PreciseDiff> {
+    Ok(time::precise_diff(start, end).into())
+}
+
+#[pyfunction]
+fn add_duration(stamp: u64, secs: u64) -> PyResult<u64> {
+    Ok(time::add_duration(stamp, secs))
+}
+
+#[pyfunction]
+fn add_calendar(stamp: u64, years: i32, months: i32, days: i32) -> PyResult<u64> {
+    Ok(time::add_calendar(stamp, years, months, days))
+}
+
 #[pyfunction]
 fn sum(a: usize, b: usize) -> PyResult<usize> {
     Ok(a + b)
@@ -30,5 +95,12 @@ fn rst(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(pyo3::wrap_pyfunction!(sum, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_duration, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(to_iso, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_humantime, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(to_iso_tz, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_iso, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(precise_diff, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(add_duration, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(add_calendar, m)?)?;
+    m.add_class::<PreciseDiff>()?;
     Ok(())
 }
\ No newline at end of file